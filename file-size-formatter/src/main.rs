@@ -1,9 +1,18 @@
 /// This module provides utilities for handling file sizes, allowing users to input a string
-/// representing the size and unit (e.g., "300 kb" or "12 mb"). It defines a `Sizes` struct
+/// representing the size and unit, either spaced ("300 kb") or glued ("300kb"). It defines
+/// a `Sizes` struct
 /// that holds the file size in various units (bytes, kilobytes, megabytes, gigabytes) and
 /// returns a debug representation of this struct. The module includes an enum `FileSize`
 /// with variants for different size units and functions to parse the input string and
 /// format the file sizes accordingly.
+///
+/// Besides the SI (power-of-ten) units, the module also understands IEC binary units
+/// (KiB/MiB/GiB/TiB, powers of 1024), since the two families disagree on what a "kilobyte"
+/// is and tools that only support one of them force users to convert by hand.
+///
+/// Running with `--du <path>...` switches to a small `du`-style disk-usage mode that sums
+/// file sizes under one or more paths (optionally glob patterns) instead of converting a
+/// literal size string, reusing the same `Sizes`/humanize formatting.
 
 #[derive(Debug)]
 enum FileSize {
@@ -11,101 +20,483 @@ enum FileSize {
     Kilobytes(f64),
     Megabytes(f64),
     Gigabytes(f64),
+    Terabytes(f64),
+    Kibibytes(f64),
+    Mebibytes(f64),
+    Gibibytes(f64),
+    Tebibytes(f64),
 }
 
 impl FileSize {
-    /// Parses an input string (e.g., "300 kb" or "12 mb") and returns the corresponding `FileSize`.
+    /// Parses an input string such as "300 kb", "12 mb" or glued forms like "1.5GiB"
+    /// and returns the corresponding `FileSize`. Delegates to `FromStr`.
     fn from_input(input: &str) -> Option<FileSize> {
-        let parts: Vec<&str> = input.split_whitespace().collect();
-        if parts.len() != 2 {
-            eprintln!("Invalid input format. Usage: cargo run -- <size>");
-            return None;
+        match input.parse() {
+            Ok(file_size) => Some(file_size),
+            Err(e) => {
+                eprintln!("{}", e);
+                None
+            }
+        }
+    }
+
+    /// Applies a relative size adjustment expression to a base byte count, following the
+    /// convention from file-truncation tools: a leading `+` extends `base` by the given
+    /// amount, `-` reduces it (clamped to zero), `%` rounds `base` up to the nearest
+    /// multiple of the amount, and `/` rounds it down to a multiple.
+    fn apply_adjustment(base: u64, expr: &str) -> Result<u64, String> {
+        let trimmed = expr.trim();
+        let mut chars = trimmed.chars();
+        let op = chars
+            .next()
+            .ok_or_else(|| "Empty adjustment expression".to_string())?;
+
+        let rest = chars.as_str().trim();
+        if rest.is_empty() {
+            return Err(format!("Adjustment '{}' is missing an amount", expr));
         }
+        let amount_size: FileSize = rest.parse()?;
+        let amount = amount_size.to_bytes().round() as u64;
 
-        let size: f64 = match parts[0].parse() {
-            Ok(val) => val,
-            Err(_) => {
-                eprintln!("Invalid size value: {}", parts[0]);
-                return None;
+        match op {
+            '+' => Ok(base.saturating_add(amount)),
+            '-' => Ok(base.saturating_sub(amount)),
+            '%' => {
+                if amount == 0 {
+                    return Err("Cannot round to a multiple of zero".to_string());
+                }
+                let remainder = base % amount;
+                if remainder == 0 {
+                    Ok(base)
+                } else {
+                    Ok(base.saturating_add(amount - remainder))
+                }
             }
-        };
+            '/' => {
+                if amount == 0 {
+                    return Err("Cannot round to a multiple of zero".to_string());
+                }
+                Ok(base - (base % amount))
+            }
+            other => Err(format!(
+                "Unknown adjustment operator '{}' (expected +, -, % or /)",
+                other
+            )),
+        }
+    }
+
+    /// Converts the size to a raw byte count, regardless of which unit it was expressed in.
+    fn to_bytes(&self) -> f64 {
+        match *self {
+            FileSize::Bytes(b) => b as f64,
+            FileSize::Kilobytes(kb) => kb * 1000.0,
+            FileSize::Megabytes(mb) => mb * 1_000_000.0,
+            FileSize::Gigabytes(gb) => gb * 1_000_000_000.0,
+            FileSize::Terabytes(tb) => tb * 1_000_000_000_000.0,
+            FileSize::Kibibytes(kib) => kib * 1024.0,
+            FileSize::Mebibytes(mib) => mib * 1024.0f64.powi(2),
+            FileSize::Gibibytes(gib) => gib * 1024.0f64.powi(3),
+            FileSize::Tebibytes(tib) => tib * 1024.0f64.powi(4),
+        }
+    }
+}
+
+impl std::str::FromStr for FileSize {
+    type Err = String;
+
+    /// Parses both spaced ("300 kb") and glued ("300kb") forms: a leading run of ASCII
+    /// digits and at most one `.` is taken as the number, and whatever follows (after
+    /// skipping any whitespace) is taken as the unit suffix. A bare number with no
+    /// suffix parses as `Bytes`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let trimmed = input.trim();
+        let mut chars = trimmed.chars().peekable();
+
+        let mut number = String::new();
+        let mut seen_dot = false;
+        while let Some(&c) = chars.peek() {
+            if c.is_ascii_digit() {
+                number.push(c);
+                chars.next();
+            } else if c == '.' && !seen_dot {
+                seen_dot = true;
+                number.push(c);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        if number.is_empty() {
+            return Err(format!("Invalid size value: {}", trimmed));
+        }
+
+        let size: f64 = number
+            .parse()
+            .map_err(|_| format!("Invalid size value: {}", number))?;
 
         if size < 0.0 {
-            eprintln!("Size cannot be negative: {}", size);
-            return None;
+            return Err(format!("Size cannot be negative: {}", size));
         }
 
-        let unit = parts[1].to_lowercase();
+        let suffix: String = chars.collect::<String>().trim().to_lowercase();
 
-        match unit.as_str() {
-            "bytes" => Some(FileSize::Bytes(size as u64)),
-            "kb" => Some(FileSize::Kilobytes(size)),
-            "mb" => Some(FileSize::Megabytes(size)),
-            "gb" => Some(FileSize::Gigabytes(size)),
-            _ => {
-                eprintln!("Unknown unit: {}", unit);
-                None
+        match suffix.as_str() {
+            "" | "b" | "bytes" => Ok(FileSize::Bytes(size as u64)),
+            "kb" | "k" => Ok(FileSize::Kilobytes(size)),
+            "mb" | "m" => Ok(FileSize::Megabytes(size)),
+            "gb" | "g" => Ok(FileSize::Gigabytes(size)),
+            "tb" => Ok(FileSize::Terabytes(size)),
+            "kib" => Ok(FileSize::Kibibytes(size)),
+            "mib" => Ok(FileSize::Mebibytes(size)),
+            "gib" => Ok(FileSize::Gibibytes(size)),
+            "tib" => Ok(FileSize::Tebibytes(size)),
+            other => Err(format!("Unknown unit: {}", other)),
+        }
+    }
+}
+
+/// Which family of units to prefer when printing the human-readable summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Base {
+    /// Power-of-ten units: kB, MB, GB.
+    Si,
+    /// Power-of-two units: KiB, MiB, GiB, TiB.
+    Binary,
+}
+
+impl Base {
+    /// Parses the `--base`/`-2`/`-k` CLI flags out of the arguments following the size input.
+    ///
+    /// `-2` selects base 1024 (binary), `-k` selects base 1000 (SI), and `--base <1000|1024>`
+    /// (or `--base=<1000|1024>`) spells either one out explicitly. Defaults to `Base::Si`.
+    fn from_args(args: &[String]) -> Result<Base, String> {
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-2" => return Ok(Base::Binary),
+                "-k" => return Ok(Base::Si),
+                "--base" => {
+                    let value = iter
+                        .next()
+                        .ok_or_else(|| "--base requires a value: 1000 or 1024".to_string())?;
+                    return Base::from_value(value);
+                }
+                other if other.starts_with("--base=") => {
+                    return Base::from_value(&other["--base=".len()..]);
+                }
+                _ => {}
             }
         }
+        Ok(Base::Si)
+    }
+
+    fn from_value(value: &str) -> Result<Base, String> {
+        match value {
+            "1000" => Ok(Base::Si),
+            "1024" => Ok(Base::Binary),
+            other => Err(format!("Invalid base: {} (expected 1000 or 1024)", other)),
+        }
     }
 }
 
 #[derive(Debug)]
 struct Sizes {
+    bytes_raw: u64,
     bytes: String,
     kilobytes: String,
     megabytes: String,
     gigabytes: String,
+    kibibytes: String,
+    mebibytes: String,
+    gibibytes: String,
+    tebibytes: String,
 }
 
 impl Sizes {
-    /// Creates a new `Sizes` instance from a `FileSize`.
+    /// Creates a new `Sizes` instance from a `FileSize`, filling in both the SI (kB/MB/GB)
+    /// and the binary (KiB/MiB/GiB/TiB) columns from the underlying byte count.
     fn from_file_size(file_size: FileSize) -> Sizes {
-        match file_size {
-            FileSize::Bytes(b) => Sizes {
-                bytes: format!("{} bytes", b),
-                kilobytes: format!("{:.2} KB", b as f64 / 1000.0),
-                megabytes: format!("{:.2} MB", b as f64 / 1_000_000.0),
-                gigabytes: format!("{:.2} GB", b as f64 / 1_000_000_000.0),
-            },
-            FileSize::Kilobytes(kb) => Sizes {
-                bytes: format!("{} bytes", (kb * 1000.0) as u64),
-                kilobytes: format!("{:.2} KB", kb),
-                megabytes: format!("{:.2} MB", kb / 1000.0),
-                gigabytes: format!("{:.2} GB", kb / 1_000_000.0),
-            },
-            FileSize::Megabytes(mb) => Sizes {
-                bytes: format!("{} bytes", (mb * 1_000_000.0) as u64),
-                kilobytes: format!("{:.2} KB", mb * 1000.0),
-                megabytes: format!("{:.2} MB", mb),
-                gigabytes: format!("{:.2} GB", mb / 1000.0),
-            },
-            FileSize::Gigabytes(gb) => Sizes {
-                bytes: format!("{} bytes", (gb * 1_000_000_000.0) as u64),
-                kilobytes: format!("{:.2} KB", gb * 1_000_000.0),
-                megabytes: format!("{:.2} MB", gb * 1000.0),
-                gigabytes: format!("{:.2} GB", gb),
-            },
+        let b = file_size.to_bytes();
+        Sizes {
+            bytes_raw: b as u64,
+            bytes: format!("{} bytes", b as u64),
+            kilobytes: format!("{:.2} kB", b / 1000.0),
+            megabytes: format!("{:.2} MB", b / 1_000_000.0),
+            gigabytes: format!("{:.2} GB", b / 1_000_000_000.0),
+            kibibytes: format!("{:.2} KiB", b / 1024.0),
+            mebibytes: format!("{:.2} MiB", b / 1024.0f64.powi(2)),
+            gibibytes: format!("{:.2} GiB", b / 1024.0f64.powi(3)),
+            tebibytes: format!("{:.2} TiB", b / 1024.0f64.powi(4)),
+        }
+    }
+
+    /// Picks the largest unit in which the value is at least 1 and renders it with two
+    /// decimal places, honoring the SI/binary base choice (e.g. `1.50 MB` vs `1.43 MiB`).
+    fn humanize(&self, base: Base) -> String {
+        let b = self.bytes_raw as f64;
+        match base {
+            Base::Si => {
+                if b >= 1_000_000_000.0 {
+                    format!("{:.2} GB", b / 1_000_000_000.0)
+                } else if b >= 1_000_000.0 {
+                    format!("{:.2} MB", b / 1_000_000.0)
+                } else if b >= 1000.0 {
+                    format!("{:.2} kB", b / 1000.0)
+                } else {
+                    format!("{} bytes", self.bytes_raw)
+                }
+            }
+            Base::Binary => {
+                if b >= 1024.0f64.powi(4) {
+                    format!("{:.2} TiB", b / 1024.0f64.powi(4))
+                } else if b >= 1024.0f64.powi(3) {
+                    format!("{:.2} GiB", b / 1024.0f64.powi(3))
+                } else if b >= 1024.0f64.powi(2) {
+                    format!("{:.2} MiB", b / 1024.0f64.powi(2))
+                } else if b >= 1024.0 {
+                    format!("{:.2} KiB", b / 1024.0)
+                } else {
+                    format!("{} bytes", self.bytes_raw)
+                }
+            }
+        }
+    }
+
+    /// Prints the unit rows for the requested base (SI or binary).
+    fn print(&self, base: Base) {
+        println!("Bytes: {}", self.bytes);
+        match base {
+            Base::Si => {
+                println!("Kilobytes: {}", self.kilobytes);
+                println!("Megabytes: {}", self.megabytes);
+                println!("Gigabytes: {}", self.gigabytes);
+            }
+            Base::Binary => {
+                println!("Kibibytes: {}", self.kibibytes);
+                println!("Mebibytes: {}", self.mebibytes);
+                println!("Gibibytes: {}", self.gibibytes);
+                println!("Tebibytes: {}", self.tebibytes);
+            }
+        }
+    }
+}
+
+/// An adjustment expression starts with one of these operators; anything else in the
+/// second CLI position is treated as a `--base`-style flag instead. `-2`/`-k`/`--base...`
+/// are reserved for `Base::from_args`, so they never count as an adjustment even though
+/// `-2` also starts with the `-` operator.
+fn is_adjustment(arg: &str) -> bool {
+    arg.starts_with(['+', '-', '%', '/']) && !matches!(arg, "-2" | "-k") && !arg.starts_with("--base")
+}
+
+/// Recursively sums the byte length of every file under `path`. Symlinks are not
+/// followed (matching `du`'s default behavior) so a symlinked directory contributes
+/// only its own entry size instead of double-counting the tree it points at or
+/// walking into a symlink cycle. If `persistent` is set, directories that can't be
+/// read (permission errors, races with deletion, etc.) are skipped and contribute
+/// zero instead of aborting the whole scan.
+fn dir_size(path: &std::path::Path, persistent: bool) -> Result<u64, String> {
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) if persistent => return Ok(0),
+        Err(e) => return Err(format!("Cannot stat {}: {}", path.display(), e)),
+    };
+
+    if !metadata.is_dir() {
+        return Ok(metadata.len());
+    }
+
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) if persistent => return Ok(0),
+        Err(e) => return Err(format!("Cannot read directory {}: {}", path.display(), e)),
+    };
+
+    let mut total = 0u64;
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(_) if persistent => continue,
+            Err(e) => return Err(format!("Error reading entry in {}: {}", path.display(), e)),
+        };
+        match dir_size(&entry.path(), persistent) {
+            Ok(size) => total += size,
+            Err(_) if persistent => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(total)
+}
+
+/// Matches a file name against a pattern containing at most one `*` wildcard.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        }
+        None => pattern == name,
+    }
+}
+
+/// Expands a path that may contain a glob pattern (e.g. `logs/*.log`) in its last
+/// component into the list of matching paths. A pattern with no `*` is returned as-is.
+fn expand_glob(pattern: &str) -> Vec<std::path::PathBuf> {
+    if !pattern.contains('*') {
+        return vec![std::path::PathBuf::from(pattern)];
+    }
+
+    let (dir, file_pattern) = match pattern.rsplit_once('/') {
+        Some((d, f)) => (std::path::PathBuf::from(if d.is_empty() { "/" } else { d }), f),
+        None => (std::path::PathBuf::from("."), pattern),
+    };
+
+    let mut matches: Vec<std::path::PathBuf> = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| wildcard_match(file_pattern, name))
+            })
+            .map(|entry| entry.path())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    matches.sort();
+    matches
+}
+
+/// Runs the `--du` disk-usage mode: sums recursive file sizes for one or more paths
+/// (which may contain glob patterns) and prints each through the `Sizes`/humanize
+/// formatting. `--total` additionally prints the grand total across all paths,
+/// `--minimal` prints only the aggregate number with no extra output, and
+/// `--persistent` keeps scanning past unreadable subdirectories.
+fn run_du(args: &[String]) {
+    let mut patterns: Vec<String> = Vec::new();
+    let mut base_flags: Vec<String> = Vec::new();
+    let mut total = false;
+    let mut minimal = false;
+    let mut persistent = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--total" => total = true,
+            "--minimal" => minimal = true,
+            "--persistent" => persistent = true,
+            "-2" | "-k" => base_flags.push(arg.clone()),
+            "--base" => {
+                base_flags.push(arg.clone());
+                if let Some(value) = iter.next() {
+                    base_flags.push(value.clone());
+                }
+            }
+            other if other.starts_with("--base=") => base_flags.push(other.to_string()),
+            other => patterns.push(other.to_string()),
+        }
+    }
+
+    let base = match Base::from_args(&base_flags) {
+        Ok(base) => base,
+        Err(e) => {
+            if !minimal {
+                eprintln!("{}", e);
+            }
+            return;
+        }
+    };
+
+    if patterns.is_empty() {
+        if !minimal {
+            eprintln!("No paths provided. Usage: cargo run -- --du <path>... [--total] [--minimal] [--persistent]");
+        }
+        return;
+    }
+
+    let mut grand_total: u64 = 0;
+
+    for pattern in &patterns {
+        let matches = expand_glob(pattern);
+        if matches.is_empty() {
+            if !minimal {
+                eprintln!("No matches for path: {}", pattern);
+            }
+            continue;
+        }
+
+        let mut pattern_total: u64 = 0;
+        for path in &matches {
+            match dir_size(path, persistent) {
+                Ok(size) => pattern_total += size,
+                Err(e) => {
+                    if !minimal {
+                        eprintln!("{}", e);
+                    }
+                    if persistent {
+                        continue;
+                    }
+                    return;
+                }
+            }
+        }
+
+        grand_total += pattern_total;
+        if !minimal {
+            let sizes = Sizes::from_file_size(FileSize::Bytes(pattern_total));
+            println!("{}: {}", pattern, sizes.humanize(base));
+        }
+    }
+
+    if total || minimal {
+        let sizes = Sizes::from_file_size(FileSize::Bytes(grand_total));
+        if minimal {
+            print!("{}", sizes.humanize(base));
+        } else {
+            println!("Total: {}", sizes.humanize(base));
         }
     }
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.get(1).map(String::as_str) == Some("--du") {
+        run_du(&args[2..]);
+        return;
+    }
+
     // Example usage:
-    if let Some(input) = std::env::args().nth(1) {
-        if let Some(file_size) = FileSize::from_input(&input) {
-            let sizes = Sizes::from_file_size(file_size);
-            // Bytes
-            println!("Bytes: {}", sizes.bytes);
-            // Kilobytes
-            println!("Kilobytes: {}", sizes.kilobytes);
-            // Megabytes
-            println!("Megabytes: {}", sizes.megabytes);
-            // Gigabytes
-            println!("Gigabytes: {}", sizes.gigabytes);
+    if let Some(input) = args.get(1) {
+        if let Some(file_size) = FileSize::from_input(input) {
+            let mut bytes = file_size.to_bytes().round() as u64;
+            let mut rest = &args[2..];
+
+            if let Some(adjustment) = rest.first().filter(|a| is_adjustment(a)) {
+                bytes = match FileSize::apply_adjustment(bytes, adjustment) {
+                    Ok(adjusted) => adjusted,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                };
+                rest = &rest[1..];
+            }
+
+            let base = match Base::from_args(rest) {
+                Ok(base) => base,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let sizes = Sizes::from_file_size(FileSize::Bytes(bytes));
+            println!("{}", sizes.humanize(base));
+            sizes.print(base);
             println!("Sizes: {:?}", sizes);
-        } else {
-            eprintln!("Invalid input format. Usage: cargo run -- <size>");
         }
     } else {
         eprintln!("No file size provided. Usage: cargo run -- <size>");